@@ -0,0 +1,337 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+
+use dioxus::prelude::*;
+
+use crate::tree::{NodeId, Tree};
+
+/// Filesystem-backed page tree. Each node's `content` holds the node's
+/// path (the root as passed to `new`, children joined onto it); directory nodes are expanded lazily from disk the first
+/// time they're opened, reusing `Tree`'s own `is_expanded`/`children`
+/// bookkeeping instead of a parallel model.
+#[derive(Debug, Clone)]
+pub struct PageExplorer {
+    tree: Tree,
+    loaded: HashSet<NodeId>,
+}
+
+impl PageExplorer {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        let mut tree = Tree::new();
+        let root_id = tree.add_node(root.into().display().to_string(), None);
+
+        let mut explorer = Self {
+            tree,
+            loaded: HashSet::new(),
+        };
+        explorer.ensure_loaded(root_id);
+        explorer
+    }
+
+    pub fn tree(&self) -> &Tree {
+        &self.tree
+    }
+
+    pub fn path_of(&self, id: NodeId) -> Option<PathBuf> {
+        self.tree.get_node(id).map(|n| PathBuf::from(&n.content))
+    }
+
+    pub fn is_dir(&self, id: NodeId) -> bool {
+        self.path_of(id).map(|p| p.is_dir()).unwrap_or(false)
+    }
+
+    pub fn name(&self, id: NodeId) -> String {
+        self.path_of(id)
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .unwrap_or_default()
+    }
+
+    /// Populate a directory node's children from disk, once. Calling this
+    /// again on an already-loaded node (or a non-directory) is a no-op.
+    pub fn ensure_loaded(&mut self, id: NodeId) {
+        if self.loaded.contains(&id) {
+            return;
+        }
+        self.loaded.insert(id);
+
+        let Some(path) = self.path_of(id) else {
+            return;
+        };
+        if !path.is_dir() {
+            return;
+        }
+
+        let mut entries: Vec<PathBuf> = fs::read_dir(&path)
+            .map(|read_dir| read_dir.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect())
+            .unwrap_or_default();
+
+        entries.sort_by(|a, b| {
+            b.is_dir()
+                .cmp(&a.is_dir())
+                .then_with(|| a.file_name().cmp(&b.file_name()))
+        });
+
+        for entry in entries {
+            let is_page = entry.is_dir() || entry.extension().is_some_and(|ext| ext == "md");
+            if is_page {
+                let child = self.tree.add_node(entry.display().to_string(), Some(id));
+                // Subdirectories start collapsed so their first toggle loads them.
+                if entry.is_dir() {
+                    if let Some(node) = self.tree.get_node_mut(child) {
+                        node.is_expanded = false;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Toggle a directory's expanded state, lazily loading its children
+    /// the first time it's opened. No-op for leaf (page) nodes.
+    pub fn toggle_expanded(&mut self, id: NodeId) {
+        if !self.is_dir(id) {
+            return;
+        }
+
+        let is_expanded = self.tree.get_node(id).map(|n| n.is_expanded).unwrap_or(false);
+        if !is_expanded {
+            self.ensure_loaded(id);
+        }
+        self.tree.toggle_expanded(id);
+    }
+
+    /// Expand every ancestor of `id` (loading their children as needed) so
+    /// that `id` itself becomes visible in the tree.
+    pub fn reveal(&mut self, id: NodeId) {
+        let mut current = self.tree.get_node(id).and_then(|n| n.parent);
+
+        while let Some(ancestor) = current {
+            self.ensure_loaded(ancestor);
+            if let Some(node) = self.tree.get_node_mut(ancestor) {
+                node.is_expanded = true;
+            }
+            current = self.tree.get_node(ancestor).and_then(|n| n.parent);
+        }
+    }
+
+    /// Visible nodes in display order (depth-first, skipping collapsed
+    /// subtrees) — the order keyboard up/down navigation should follow.
+    pub fn visible_nodes(&self) -> Vec<NodeId> {
+        fn walk(tree: &Tree, id: NodeId, out: &mut Vec<NodeId>) {
+            out.push(id);
+            if let Some(node) = tree.get_node(id) {
+                if node.is_expanded {
+                    for &child in &node.children {
+                        walk(tree, child, out);
+                    }
+                }
+            }
+        }
+
+        let mut out = Vec::new();
+        for &root in self.tree.get_root_nodes() {
+            walk(&self.tree, root, &mut out);
+        }
+        out
+    }
+
+    /// The name a `[[wikilink]]`/`((block-ref))` elsewhere in the vault
+    /// would use to address this page: its filename without extension,
+    /// normalized the same way `extract_references` normalizes link labels
+    /// (trimmed, lowercased) so the two actually compare equal.
+    pub fn page_key(&self, id: NodeId) -> Option<String> {
+        self.path_of(id)?
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().trim().to_lowercase())
+    }
+
+    /// Recursively collect every `.md` file under the explorer's root,
+    /// independent of what the lazily-loaded tree widget currently has
+    /// expanded.
+    fn markdown_files(&self) -> Vec<PathBuf> {
+        fn walk(dir: &std::path::Path, out: &mut Vec<PathBuf>) {
+            let Ok(read_dir) = fs::read_dir(dir) else {
+                return;
+            };
+            for entry in read_dir.filter_map(|entry| entry.ok()) {
+                let path = entry.path();
+                if path.is_dir() {
+                    walk(&path, out);
+                } else if path.extension().is_some_and(|ext| ext == "md") {
+                    out.push(path);
+                }
+            }
+        }
+
+        let mut out = Vec::new();
+        if let Some(&root) = self.tree.get_root_nodes().first() {
+            if let Some(root_path) = self.path_of(root) {
+                walk(&root_path, &mut out);
+            }
+        }
+        out
+    }
+
+    /// Vault-wide "Linked References": read every markdown page under the
+    /// root and index which pages reference each page key, so a page can
+    /// show who links to *it* rather than only who it links to. Re-scans
+    /// disk on every call, which is fine for an interactive sidebar but
+    /// not for a hot loop.
+    pub fn linked_references(&self) -> HashMap<String, Vec<(PathBuf, String)>> {
+        let mut index: HashMap<String, Vec<(PathBuf, String)>> = HashMap::new();
+
+        for path in self.markdown_files() {
+            let Ok(contents) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let page_tree = Tree::from_markdown(&contents);
+
+            for (key, node_ids) in page_tree.backlinks() {
+                for node_id in node_ids {
+                    if let Some(node) = page_tree.get_node(node_id) {
+                        index.entry(key.clone()).or_default().push((path.clone(), node.content.clone()));
+                    }
+                }
+            }
+        }
+
+        index
+    }
+}
+
+/// A reusable collapsible tree widget for browsing the page explorer.
+/// Handles keyboard navigation (up/down to move selection, left/right to
+/// collapse/expand, Enter to open) in addition to mouse interaction.
+#[component]
+pub fn TreeView(
+    explorer: Signal<PageExplorer>,
+    selected: Signal<Option<NodeId>>,
+    on_open: EventHandler<NodeId>,
+) -> Element {
+    let handle_keydown = move |evt: Event<KeyboardData>| {
+        let Some(current) = selected() else { return };
+        let key = evt.data().key();
+
+        match key {
+            Key::ArrowDown | Key::ArrowUp => {
+                evt.prevent_default();
+                let visible = explorer.read().visible_nodes();
+                if let Some(pos) = visible.iter().position(|&id| id == current) {
+                    let next_pos = if key == Key::ArrowDown {
+                        (pos + 1).min(visible.len().saturating_sub(1))
+                    } else {
+                        pos.saturating_sub(1)
+                    };
+                    selected.set(Some(visible[next_pos]));
+                }
+            }
+            Key::ArrowRight => {
+                evt.prevent_default();
+                let is_expanded = explorer.read().tree().get_node(current).map(|n| n.is_expanded).unwrap_or(false);
+                if explorer.read().is_dir(current) && !is_expanded {
+                    explorer.write().toggle_expanded(current);
+                }
+            }
+            Key::ArrowLeft => {
+                evt.prevent_default();
+                let is_expanded = explorer.read().tree().get_node(current).map(|n| n.is_expanded).unwrap_or(false);
+                if explorer.read().is_dir(current) && is_expanded {
+                    explorer.write().toggle_expanded(current);
+                } else if let Some(parent) = explorer.read().tree().get_node(current).and_then(|n| n.parent) {
+                    selected.set(Some(parent));
+                }
+            }
+            Key::Enter => {
+                evt.prevent_default();
+                if explorer.read().is_dir(current) {
+                    explorer.write().toggle_expanded(current);
+                } else {
+                    on_open.call(current);
+                }
+            }
+            _ => {}
+        }
+    };
+
+    rsx! {
+        div {
+            class: "tree-view",
+            tabindex: "0",
+            onkeydown: handle_keydown,
+
+            for root_id in explorer.read().tree().get_root_nodes().to_vec() {
+                TreeViewNode {
+                    node_id: root_id,
+                    explorer,
+                    selected,
+                    on_open,
+                    depth: 0,
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn TreeViewNode(
+    node_id: NodeId,
+    explorer: Signal<PageExplorer>,
+    selected: Signal<Option<NodeId>>,
+    on_open: EventHandler<NodeId>,
+    depth: usize,
+) -> Element {
+    let node = explorer.read().tree().get_node(node_id).cloned();
+    let Some(node) = node else {
+        return rsx! { div { "Missing node" } };
+    };
+
+    let name = explorer.read().name(node_id);
+    let is_dir = explorer.read().is_dir(node_id);
+    let is_selected = selected() == Some(node_id);
+    let is_expanded = node.is_expanded;
+
+    rsx! {
+        div {
+            class: "tree-view-node",
+
+            div {
+                id: "tree-node-{node_id.0}",
+                class: if is_selected { "tree-view-row selected" } else { "tree-view-row" },
+                style: "margin-left: {depth * 16}px",
+                onclick: move |_| {
+                    selected.set(Some(node_id));
+                    if is_dir {
+                        explorer.write().toggle_expanded(node_id);
+                    } else {
+                        on_open.call(node_id);
+                    }
+                },
+
+                span {
+                    class: "tree-view-icon",
+                    if is_dir {
+                        if is_expanded { "📂" } else { "📁" }
+                    } else {
+                        "📄"
+                    }
+                }
+                span { class: "tree-view-label", "{name}" }
+            }
+
+            if is_dir && is_expanded {
+                div {
+                    class: "tree-view-children",
+                    for child_id in node.children.iter() {
+                        TreeViewNode {
+                            node_id: *child_id,
+                            explorer,
+                            selected,
+                            on_open,
+                            depth: depth + 1,
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -6,5 +6,8 @@ pub use outliner::Outliner;
 mod tree;
 pub use tree::Tree;
 
+mod explorer;
+pub use explorer::{PageExplorer, TreeView};
+
 mod markdown;
-pub use markdown::render_markdown;
+pub use markdown::{extract_outline, parse_table, render_markdown, OutlineEntry, Table};
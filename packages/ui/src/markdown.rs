@@ -1,13 +1,14 @@
 use comrak::{
-    nodes::NodeValue,
+    format_html,
+    nodes::{Ast, AstNode, LineColumn, NodeValue},
     parse_document,
+    Anchorizer,
     Arena,
     Options,
-    markdown_to_html,
 };
+use std::cell::RefCell;
 
-/// Render markdown to HTML using Comrak with native math enabled.
-pub fn render_markdown(markdown: &str) -> String {
+pub(crate) fn parser_options() -> Options {
     let mut options = Options::default();
 
     // Extensions (use the exact Extension field names from the docs)
@@ -18,33 +19,35 @@ pub fn render_markdown(markdown: &str) -> String {
     options.extension.tasklist = true;
     options.extension.autolink = true;
     options.extension.superscript = true;
-    // To enable header IDs (anchor links), set header_ids to Some(prefix)
-    // options.extension.header_ids = Some("user-content-".to_string());
 
     // Native math parsing
     options.extension.math_dollars = true;
     options.extension.math_code = true;
 
-    // Render-time options: allow raw HTML if you intend to pass it through later
+    options
+}
+
+/// Render markdown to HTML using Comrak with native math, `[[wikilinks]]`,
+/// and `((block-refs))` enabled. Headings get an `id` anchor from the same
+/// scheme `extract_outline` uses, so a table-of-contents built from it
+/// resolves.
+pub fn render_markdown(markdown: &str) -> String {
+    let mut options = parser_options();
     options.render.r#unsafe = true;
+    options.extension.header_ids = Some(String::new());
+
+    let arena = Arena::new();
+    let root = parse_document(&arena, markdown, &options);
+    rewrite_reference_links(&arena, root);
 
-    markdown_to_html(markdown, &options)
+    let mut html = vec![];
+    format_html(root, &options, &mut html).expect("formatting markdown to HTML never fails");
+    String::from_utf8(html).expect("comrak always emits valid UTF-8")
 }
 
 /// Extract plain text from markdown while preserving math literal content.
 pub fn extract_plain_text(markdown: &str) -> String {
-    let mut options = Options::default();
-
-    // Match parse behavior used for rendering
-    options.extension.strikethrough = true;
-    options.extension.table = true;
-    options.extension.footnotes = true;
-    options.extension.inline_footnotes = true;
-    options.extension.tasklist = true;
-    options.extension.autolink = true;
-    options.extension.superscript = true;
-    options.extension.math_dollars = true;
-    options.extension.math_code = true;
+    let options = parser_options();
 
     let arena = Arena::new();
     let root = parse_document(&arena, markdown, &options);
@@ -66,6 +69,561 @@ pub fn extract_plain_text(markdown: &str) -> String {
     text.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
+/// A column's alignment marker in a GFM table (`---`, `:---`, `:---:`,
+/// `---:`), mirrored from Comrak's `TableAlignment` so it round-trips.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnAlignment {
+    None,
+    Left,
+    Center,
+    Right,
+}
+
+impl From<comrak::nodes::TableAlignment> for ColumnAlignment {
+    fn from(value: comrak::nodes::TableAlignment) -> Self {
+        match value {
+            comrak::nodes::TableAlignment::None => ColumnAlignment::None,
+            comrak::nodes::TableAlignment::Left => ColumnAlignment::Left,
+            comrak::nodes::TableAlignment::Center => ColumnAlignment::Center,
+            comrak::nodes::TableAlignment::Right => ColumnAlignment::Right,
+        }
+    }
+}
+
+/// One cell of a `Table`, modeled after orgize's `TableCell` element.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableCell {
+    pub content: String,
+}
+
+/// One row of a `Table`, modeled after orgize's `TableRow` element.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableRow {
+    pub cells: Vec<TableCell>,
+}
+
+/// An in-memory GFM table, split into header/body rows the way orgize
+/// splits `Table`/`TableRow`/`TableCell`, so alignment markers survive
+/// edits instead of being inferred from cell content.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Table {
+    pub alignments: Vec<ColumnAlignment>,
+    pub header: TableRow,
+    pub rows: Vec<TableRow>,
+}
+
+impl Table {
+    pub fn add_row(&mut self) {
+        let width = self.header.cells.len();
+        self.rows.push(TableRow {
+            cells: vec![TableCell::default(); width],
+        });
+    }
+
+    pub fn remove_row(&mut self, index: usize) {
+        if index < self.rows.len() {
+            self.rows.remove(index);
+        }
+    }
+
+    pub fn add_column(&mut self) {
+        self.header.cells.push(TableCell::default());
+        self.alignments.push(ColumnAlignment::None);
+        for row in &mut self.rows {
+            row.cells.push(TableCell::default());
+        }
+    }
+
+    pub fn remove_column(&mut self, index: usize) {
+        if index >= self.header.cells.len() {
+            return;
+        }
+
+        self.header.cells.remove(index);
+        self.alignments.remove(index);
+        for row in &mut self.rows {
+            if index < row.cells.len() {
+                row.cells.remove(index);
+            }
+        }
+    }
+
+    /// Serialize back to canonical GFM pipe syntax.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        write_table_row(&mut out, &self.header);
+
+        out.push('|');
+        for alignment in &self.alignments {
+            let marker = match alignment {
+                ColumnAlignment::None => " --- ",
+                ColumnAlignment::Left => " :--- ",
+                ColumnAlignment::Center => " :---: ",
+                ColumnAlignment::Right => " ---: ",
+            };
+            out.push_str(marker);
+            out.push('|');
+        }
+        out.push('\n');
+
+        for row in &self.rows {
+            write_table_row(&mut out, row);
+        }
+
+        out
+    }
+}
+
+fn write_table_row(out: &mut String, row: &TableRow) {
+    out.push('|');
+    for cell in &row.cells {
+        out.push(' ');
+        out.push_str(&cell.content.replace('|', "\\|"));
+        out.push_str(" |");
+    }
+    out.push('\n');
+}
+
+/// Parse `content` as a table if (and only if) it's a single `Table` block
+/// with nothing else alongside it; mixed content (a table plus other
+/// blocks) returns `None` so callers can fall back to plain-text editing.
+pub fn parse_table(content: &str) -> Option<Table> {
+    let options = parser_options();
+    let arena = Arena::new();
+    let root = parse_document(&arena, content, &options);
+
+    let mut children = root.children();
+    let table_node = children.next()?;
+    if children.next().is_some() {
+        return None;
+    }
+
+    table_from_node(table_node)
+}
+
+/// Build a `Table` from a `NodeValue::Table` AST node (its rows/cells, not
+/// just the top-level text), shared by `parse_table` and
+/// `write_markdown_source` so a table survives both a "user edited it in
+/// the table editor" round trip and a "whole page imported from disk" one.
+fn table_from_node<'a>(table_node: &'a AstNode<'a>) -> Option<Table> {
+    let NodeValue::Table(node_table) = &table_node.data.borrow().value else {
+        return None;
+    };
+    let alignments = node_table.alignments.iter().copied().map(ColumnAlignment::from).collect();
+
+    let mut header = TableRow::default();
+    let mut rows = Vec::new();
+
+    for row_node in table_node.children() {
+        let NodeValue::TableRow(is_header) = row_node.data.borrow().value else {
+            continue;
+        };
+
+        let cells = row_node
+            .children()
+            .map(|cell_node| TableCell {
+                content: node_markdown_literal(cell_node),
+            })
+            .collect();
+
+        if is_header {
+            header = TableRow { cells };
+        } else {
+            rows.push(TableRow { cells });
+        }
+    }
+
+    Some(Table {
+        alignments,
+        header,
+        rows,
+    })
+}
+
+/// One entry in a document's table of contents, nested under whichever
+/// preceding heading of a lower level it belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutlineEntry {
+    pub level: u8,
+    pub title: String,
+    pub id: String,
+    pub children: Vec<OutlineEntry>,
+}
+
+/// Collect every heading in a markdown document into a nested
+/// table-of-contents tree. Each entry's `id` is generated with Comrak's own
+/// `Anchorizer`, the same scheme `render_markdown` enables via
+/// `header_ids`, so entries resolve to real anchors in the rendered HTML.
+pub fn extract_outline(markdown: &str) -> Vec<OutlineEntry> {
+    let options = parser_options();
+    let arena = Arena::new();
+    let root = parse_document(&arena, markdown, &options);
+
+    let mut anchorizer = Anchorizer::new();
+    let mut flat = Vec::new();
+
+    for node in root.descendants() {
+        let level = match &node.data.borrow().value {
+            NodeValue::Heading(heading) => heading.level,
+            _ => continue,
+        };
+
+        let title = node_markdown_literal(node);
+        let id = anchorizer.anchorize(&title);
+        flat.push((level, title, id));
+    }
+
+    nest_outline(flat)
+}
+
+/// Reconstruct nesting from a flat `(level, title, id)` sequence: a heading
+/// of level N becomes a child of the nearest preceding heading with a
+/// lower level, tolerating skipped levels (e.g. H1 straight to H3).
+fn nest_outline(flat: Vec<(u8, String, String)>) -> Vec<OutlineEntry> {
+    let mut roots: Vec<OutlineEntry> = Vec::new();
+    // Stack of (level, path into `roots` to reach that heading's entry).
+    let mut stack: Vec<(u8, Vec<usize>)> = Vec::new();
+
+    for (level, title, id) in flat {
+        while matches!(stack.last(), Some((top_level, _)) if *top_level >= level) {
+            stack.pop();
+        }
+
+        let entry = OutlineEntry {
+            level,
+            title,
+            id,
+            children: Vec::new(),
+        };
+
+        let path = match stack.last() {
+            Some((_, parent_path)) => {
+                let parent = outline_entry_mut(&mut roots, parent_path);
+                parent.children.push(entry);
+                let mut path = parent_path.clone();
+                path.push(parent.children.len() - 1);
+                path
+            }
+            None => {
+                roots.push(entry);
+                vec![roots.len() - 1]
+            }
+        };
+
+        stack.push((level, path));
+    }
+
+    roots
+}
+
+fn outline_entry_mut<'a>(roots: &'a mut [OutlineEntry], path: &[usize]) -> &'a mut OutlineEntry {
+    let (&first, rest) = path.split_first().expect("path is never empty");
+    rest.iter().fold(&mut roots[first], |entry, &idx| &mut entry.children[idx])
+}
+
+/// Turn a page or block name into a stable, case-insensitive anchor slug:
+/// lowercased, trimmed, with runs of non-alphanumeric characters collapsed
+/// to a single `-`.
+pub(crate) fn slugify(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    let mut last_was_dash = true; // swallow a leading dash
+
+    for ch in name.trim().chars() {
+        if ch.is_alphanumeric() {
+            slug.extend(ch.to_lowercase());
+            last_was_dash = false;
+        } else if !last_was_dash {
+            slug.push('-');
+            last_was_dash = true;
+        }
+    }
+
+    while slug.ends_with('-') {
+        slug.pop();
+    }
+
+    slug
+}
+
+enum LinkFragment {
+    Text(String),
+    PageLink(String),
+    BlockRef(String),
+}
+
+/// Split a text run into plain-text fragments and `[[page]]` / `((block))`
+/// reference fragments. Returns `None` if the text contains no markers, so
+/// callers can skip untouched nodes cheaply.
+fn split_links(input: &str) -> Option<Vec<LinkFragment>> {
+    if !input.contains("[[") && !input.contains("((") {
+        return None;
+    }
+
+    let mut fragments = Vec::new();
+    let mut rest = input;
+
+    loop {
+        let page_start = rest.find("[[");
+        let block_start = rest.find("((");
+
+        let chosen = match (page_start, block_start) {
+            (None, None) => None,
+            (Some(p), None) => Some((p, true)),
+            (None, Some(b)) => Some((b, false)),
+            (Some(p), Some(b)) => Some(if p <= b { (p, true) } else { (b, false) }),
+        };
+
+        let Some((start, is_page)) = chosen else {
+            fragments.push(LinkFragment::Text(rest.to_string()));
+            break;
+        };
+
+        let (open, close) = if is_page { ("[[", "]]") } else { ("((", "))") };
+        let after_open = start + open.len();
+
+        let Some(end_rel) = rest[after_open..].find(close) else {
+            fragments.push(LinkFragment::Text(rest.to_string()));
+            break;
+        };
+
+        if start > 0 {
+            fragments.push(LinkFragment::Text(rest[..start].to_string()));
+        }
+
+        let label = rest[after_open..after_open + end_rel].trim().to_string();
+        fragments.push(if is_page {
+            LinkFragment::PageLink(label)
+        } else {
+            LinkFragment::BlockRef(label)
+        });
+
+        rest = &rest[after_open + end_rel + close.len()..];
+        if rest.is_empty() {
+            break;
+        }
+    }
+
+    Some(fragments)
+}
+
+/// Recover a block's literal markdown content by walking its descendants
+/// the same way `extract_plain_text` does, except inline math is re-wrapped
+/// in `$...$`/`$$...$$` instead of left bare, so it survives a markdown
+/// import/export round-trip verbatim.
+pub(crate) fn node_markdown_literal<'a>(node: &'a AstNode<'a>) -> String {
+    let mut text = String::new();
+
+    for descendant in node.descendants() {
+        match &descendant.data.borrow().value {
+            NodeValue::Text(t) => text.push_str(t.as_ref()),
+            NodeValue::Code(c) => text.push_str(&c.literal),
+            NodeValue::Math(m) => {
+                let delimiter = if m.display_math { "$$" } else { "$" };
+                text.push_str(delimiter);
+                text.push_str(&m.literal);
+                text.push_str(delimiter);
+            }
+            NodeValue::SoftBreak | NodeValue::LineBreak => text.push(' '),
+            NodeValue::HtmlInline(s) | NodeValue::Raw(s) => text.push_str(s),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+enum MarkdownSourceKind {
+    Heading(u8),
+    Text(String),
+    Code(String),
+    Math(String, bool),
+    Emph,
+    Strong,
+    Strikethrough,
+    Link(String),
+    Break,
+    Html(String),
+    Other,
+}
+
+/// Reconstruct a block's markdown *source*, not just its plain text: unlike
+/// `node_markdown_literal`, this re-emits the wrapping syntax Comrak parsed
+/// away (`#` headings, `**strong**`, `*emph*`, `~~strikethrough~~`,
+/// `` `code` ``, `[text](url)` links, GFM tables) in addition to
+/// `$math$`/`$$math$$`, so a round trip through
+/// `Tree::from_markdown`/`Tree::to_markdown` doesn't flatten formatting to
+/// plain text.
+pub(crate) fn node_markdown_source<'a>(node: &'a AstNode<'a>) -> String {
+    let mut out = String::new();
+    write_markdown_source(node, &mut out);
+    out.trim().to_string()
+}
+
+fn write_markdown_source<'a>(node: &'a AstNode<'a>, out: &mut String) {
+    if matches!(node.data.borrow().value, NodeValue::Table(_)) {
+        if let Some(table) = table_from_node(node) {
+            out.push_str(&table.to_markdown());
+        }
+        return;
+    }
+
+    let kind = {
+        let data = node.data.borrow();
+        match &data.value {
+            NodeValue::Heading(h) => MarkdownSourceKind::Heading(h.level),
+            NodeValue::Text(t) => MarkdownSourceKind::Text(t.clone()),
+            NodeValue::Code(c) => MarkdownSourceKind::Code(c.literal.clone()),
+            NodeValue::Math(m) => MarkdownSourceKind::Math(m.literal.clone(), m.display_math),
+            NodeValue::Emph => MarkdownSourceKind::Emph,
+            NodeValue::Strong => MarkdownSourceKind::Strong,
+            NodeValue::Strikethrough => MarkdownSourceKind::Strikethrough,
+            NodeValue::Link(l) => MarkdownSourceKind::Link(l.url.clone()),
+            NodeValue::SoftBreak | NodeValue::LineBreak => MarkdownSourceKind::Break,
+            NodeValue::HtmlInline(s) | NodeValue::Raw(s) => MarkdownSourceKind::Html(s.clone()),
+            _ => MarkdownSourceKind::Other,
+        }
+    };
+
+    match kind {
+        MarkdownSourceKind::Heading(level) => {
+            out.push_str(&"#".repeat(level as usize));
+            out.push(' ');
+            write_children_markdown_source(node, out);
+        }
+        MarkdownSourceKind::Text(text) => out.push_str(&text),
+        MarkdownSourceKind::Code(literal) => {
+            out.push('`');
+            out.push_str(&literal);
+            out.push('`');
+        }
+        MarkdownSourceKind::Math(literal, display_math) => {
+            let delimiter = if display_math { "$$" } else { "$" };
+            out.push_str(delimiter);
+            out.push_str(&literal);
+            out.push_str(delimiter);
+        }
+        MarkdownSourceKind::Emph => {
+            out.push('*');
+            write_children_markdown_source(node, out);
+            out.push('*');
+        }
+        MarkdownSourceKind::Strong => {
+            out.push_str("**");
+            write_children_markdown_source(node, out);
+            out.push_str("**");
+        }
+        MarkdownSourceKind::Strikethrough => {
+            out.push_str("~~");
+            write_children_markdown_source(node, out);
+            out.push_str("~~");
+        }
+        MarkdownSourceKind::Link(url) => {
+            out.push('[');
+            write_children_markdown_source(node, out);
+            out.push_str("](");
+            out.push_str(&url);
+            out.push(')');
+        }
+        MarkdownSourceKind::Break => out.push(' '),
+        MarkdownSourceKind::Html(html) => out.push_str(&html),
+        MarkdownSourceKind::Other => write_children_markdown_source(node, out),
+    }
+}
+
+fn write_children_markdown_source<'a>(node: &'a AstNode<'a>, out: &mut String) {
+    for child in node.children() {
+        write_markdown_source(child, out);
+    }
+}
+
+/// Collect the normalized (lowercased, trimmed) page/block names referenced
+/// by `[[...]]`/`((...))` markers in a content string, for building a
+/// backlinks index.
+pub(crate) fn extract_references(content: &str) -> Vec<String> {
+    let Some(fragments) = split_links(content) else {
+        return Vec::new();
+    };
+
+    fragments
+        .into_iter()
+        .filter_map(|fragment| match fragment {
+            LinkFragment::Text(_) => None,
+            LinkFragment::PageLink(name) | LinkFragment::BlockRef(name) => {
+                Some(name.trim().to_lowercase())
+            }
+        })
+        .collect()
+}
+
+fn ast_node<'a>(arena: &'a Arena<AstNode<'a>>, value: NodeValue) -> &'a AstNode<'a> {
+    let start = LineColumn { line: 0, column: 0 };
+    arena.alloc(AstNode::new(RefCell::new(Ast::new(value, start))))
+}
+
+fn text_node<'a>(arena: &'a Arena<AstNode<'a>>, text: &str) -> &'a AstNode<'a> {
+    ast_node(arena, NodeValue::Text(text.to_string()))
+}
+
+fn html_inline_node<'a>(arena: &'a Arena<AstNode<'a>>, html: String) -> &'a AstNode<'a> {
+    ast_node(arena, NodeValue::HtmlInline(html))
+}
+
+/// Build the three-node `<a class="...">label</a>` sequence for a
+/// wikilink/block-ref, styled with `css_class`. Every reference is rendered,
+/// none is dropped; callers pass one class per marker kind, so a reference
+/// to a page that doesn't exist is styled the same as one that does.
+/// `render.unsafe` must be on for the raw `<a>`/`</a>` tags to pass through
+/// untouched.
+fn link_nodes<'a>(
+    arena: &'a Arena<AstNode<'a>>,
+    url: String,
+    css_class: &str,
+    label: &str,
+) -> Vec<&'a AstNode<'a>> {
+    vec![
+        html_inline_node(arena, format!("<a class=\"{css_class}\" href=\"{url}\">")),
+        text_node(arena, label),
+        html_inline_node(arena, "</a>".to_string()),
+    ]
+}
+
+/// Walk the AST and rewrite `[[Page Name]]` / `((block-id))` text runs into
+/// real link nodes, styled as wikilinks/block-refs, before formatting.
+fn rewrite_reference_links<'a>(arena: &'a Arena<AstNode<'a>>, root: &'a AstNode<'a>) {
+    let text_nodes: Vec<&AstNode> = root
+        .descendants()
+        .filter(|node| matches!(node.data.borrow().value, NodeValue::Text(_)))
+        .collect();
+
+    for node in text_nodes {
+        let original = match &node.data.borrow().value {
+            NodeValue::Text(t) => t.clone(),
+            _ => continue,
+        };
+
+        let Some(fragments) = split_links(&original) else {
+            continue;
+        };
+
+        for fragment in fragments {
+            let new_nodes = match fragment {
+                LinkFragment::Text(text) => vec![text_node(arena, &text)],
+                LinkFragment::PageLink(name) => {
+                    link_nodes(arena, format!("#page/{}", slugify(&name)), "wikilink", &name)
+                }
+                LinkFragment::BlockRef(id) => {
+                    link_nodes(arena, format!("#block/{}", slugify(&id)), "block-ref", &id)
+                }
+            };
+            for new_node in new_nodes {
+                node.insert_before(new_node);
+            }
+        }
+
+        node.detach();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,11 +666,99 @@ mod tests {
         assert!(text.contains("c + d"));
     }
 
-#[test]
-fn test_math_structure() {
-    let html = render_markdown("$x + y$");
-    assert!(html.contains(r#"class="math""#), "Comrak parsed math but didn't output math span");
-}
+    #[test]
+    fn test_math_structure() {
+        let html = render_markdown("$x + y$");
+        assert!(html.contains(r#"class="math""#), "Comrak parsed math but didn't output math span");
+    }
 
-}
+    #[test]
+    fn test_wikilink_renders_as_anchor() {
+        let html = render_markdown("See [[Other Page]] for details.");
+        assert!(html.contains("href=\"#page/other-page\""));
+        assert!(html.contains("Other Page"));
+    }
+
+    #[test]
+    fn test_block_ref_renders_as_anchor() {
+        let html = render_markdown("Referenced in ((abc-123)).");
+        assert!(html.contains("href=\"#block/abc-123\""));
+    }
+
+    #[test]
+    fn test_slugify_trims_and_lowercases() {
+        assert_eq!(slugify("  My Page  "), "my-page");
+        assert_eq!(slugify("Multi   Word!!Title"), "multi-word-title");
+    }
+
+    #[test]
+    fn test_extract_outline_nests_by_level() {
+        let md = "# Title\n\n## Section A\n\n### Sub A.1\n\n## Section B\n";
+        let outline = extract_outline(md);
 
+        assert_eq!(outline.len(), 1);
+        assert_eq!(outline[0].title, "Title");
+        assert_eq!(outline[0].children.len(), 2);
+        assert_eq!(outline[0].children[0].title, "Section A");
+        assert_eq!(outline[0].children[0].children[0].title, "Sub A.1");
+        assert_eq!(outline[0].children[1].title, "Section B");
+    }
+
+    #[test]
+    fn test_extract_outline_tolerates_skipped_levels() {
+        let md = "# Title\n\n### Deep Section\n";
+        let outline = extract_outline(md);
+
+        assert_eq!(outline.len(), 1);
+        assert_eq!(outline[0].children.len(), 1);
+        assert_eq!(outline[0].children[0].title, "Deep Section");
+    }
+
+    #[test]
+    fn test_outline_ids_match_rendered_heading_ids() {
+        let md = "# My Heading";
+        let outline = extract_outline(md);
+        let html = render_markdown(md);
+
+        assert!(html.contains(&format!("id=\"{}\"", outline[0].id)));
+    }
+
+    #[test]
+    fn test_parse_table_round_trip() {
+        let md = "| Name | Age |\n| :--- | ---: |\n| Alice | 30 |\n| Bob | 25 |\n";
+        let table = parse_table(md).expect("should parse as a table");
+
+        assert_eq!(table.alignments, vec![ColumnAlignment::Left, ColumnAlignment::Right]);
+        assert_eq!(table.header.cells[0].content, "Name");
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.rows[0].cells[1].content, "30");
+
+        let regenerated = parse_table(&table.to_markdown()).expect("regenerated markdown should also parse");
+        assert_eq!(regenerated, table);
+    }
+
+    #[test]
+    fn test_parse_table_rejects_mixed_content() {
+        let md = "Some text\n\n| A | B |\n| --- | --- |\n| 1 | 2 |\n";
+        assert!(parse_table(md).is_none());
+    }
+
+    #[test]
+    fn test_table_add_remove_row_and_column() {
+        let mut table = parse_table("| A | B |\n| --- | --- |\n| 1 | 2 |\n").unwrap();
+
+        table.add_row();
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.rows[1].cells.len(), 2);
+
+        table.add_column();
+        assert_eq!(table.header.cells.len(), 3);
+        assert_eq!(table.alignments.len(), 3);
+
+        table.remove_row(0);
+        assert_eq!(table.rows.len(), 1);
+
+        table.remove_column(0);
+        assert_eq!(table.header.cells.len(), 2);
+    }
+}
@@ -1,10 +1,20 @@
+use std::fs;
+
+use dioxus::document;
 use dioxus::prelude::*;
-use crate::markdown::render_markdown;
+use crate::explorer::{PageExplorer, TreeView};
+use crate::markdown::{extract_outline, parse_table, render_markdown, OutlineEntry, Table};
 use crate::tree::{NodeId, Tree};
 
 
 #[component]
 pub fn Outliner() -> Element {
+    let explorer = use_signal(|| {
+        let root = std::env::current_dir().unwrap_or_default().join("notes");
+        PageExplorer::new(root)
+    });
+    let selected_page = use_signal(|| None::<NodeId>);
+
     let mut tree = use_signal(|| {
         let mut t = Tree::new();
 
@@ -26,6 +36,62 @@ pub fn Outliner() -> Element {
     });
 
     let mut sidebar_open = use_signal(|| true);
+    let mut outline_open = use_signal(|| false);
+    let outline = use_memo(move || extract_outline(&tree.read().to_markdown()));
+
+    // `reveal()` expanding ancestors and opening the sidebar only take
+    // effect on the next render; scrolling the revealed row into view has
+    // to wait for that render to actually land in the DOM, so it's done
+    // here (after render) rather than inline in the button's onclick.
+    let mut reveal_target = use_signal(|| None::<NodeId>);
+    use_effect(move || {
+        if let Some(page_id) = reveal_target() {
+            document::eval(&format!(
+                r#"document.getElementById("tree-node-{}")?.scrollIntoView({{behavior: "smooth", block: "center"}});"#,
+                page_id.0,
+            ));
+            reveal_target.set(None);
+        }
+    });
+
+    // Who links to the currently open page, scanned across every page in
+    // the vault (not just nodes within the page itself) and keyed by the
+    // page's filename without extension, which is what a `[[wikilink]]` elsewhere would
+    // actually use to address it.
+    let linked_references = use_memo(move || {
+        let Some(page_id) = selected_page() else {
+            return Vec::new();
+        };
+        let Some(key) = explorer.read().page_key(page_id) else {
+            return Vec::new();
+        };
+        explorer.read().linked_references().get(&key).cloned().unwrap_or_default()
+    });
+
+    // Open a page selected in the explorer: load its markdown file and
+    // parse it into the outliner's tree, preserving list nesting.
+    let open_page = move |page_id: NodeId| {
+        let Some(path) = explorer.read().path_of(page_id) else {
+            return;
+        };
+        let Ok(contents) = fs::read_to_string(&path) else {
+            return;
+        };
+
+        tree.set(Tree::from_markdown(&contents));
+    };
+
+    // Save the outliner's tree back to the currently open page as nested
+    // Markdown.
+    let save_page = move |_| {
+        let Some(page_id) = selected_page() else {
+            return;
+        };
+        let Some(path) = explorer.read().path_of(page_id) else {
+            return;
+        };
+        let _ = fs::write(path, tree.read().to_markdown());
+    };
 
     rsx! {
 
@@ -48,10 +114,32 @@ pub fn Outliner() -> Element {
 
                     div {
                         class: "bg-white",
-                        div { class: "sidebar-item active", "Main Page" }
-                        div { class: "sidebar-item", "Journal" }
-                        div { class: "sidebar-item", "Bookmarks" }
-                        div { class: "sidebar-item", "Archive" }
+                        TreeView {
+                            explorer,
+                            selected: selected_page,
+                            on_open: open_page,
+                        }
+                    }
+
+                    div {
+                        class: "linked-references",
+                        h3 { "Linked References" }
+                        if linked_references().is_empty() {
+                            div { class: "no-linked-references", "No linked references yet." }
+                        } else {
+                            ul {
+                                for (index , (source_path , content)) in linked_references().into_iter().enumerate() {
+                                    li {
+                                        key: "{index}",
+                                        span {
+                                            class: "linked-reference-source",
+                                            "{source_path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default()}"
+                                        }
+                                        span { class: "linked-reference-content", "{content}" }
+                                    }
+                                }
+                            }
+                        }
                     }
                 }
             }
@@ -71,6 +159,38 @@ pub fn Outliner() -> Element {
                     }
 
                     h1 { "📝 Outliner" }
+
+                    if let Some(page_id) = selected_page() {
+                        button {
+                            class: "reveal-current-page",
+                            onclick: move |_| {
+                                explorer.write().reveal(page_id);
+                                sidebar_open.set(true);
+                                reveal_target.set(Some(page_id));
+                            },
+                            "Reveal current page"
+                        }
+
+                        button {
+                            class: "save-page",
+                            onclick: save_page,
+                            "💾 Save"
+                        }
+                    }
+
+                    button {
+                        class: "toggle-outline",
+                        onclick: move |_| outline_open.set(!outline_open()),
+                        "📑 Outline"
+                    }
+                }
+
+                if outline_open() {
+                    div {
+                        id: "outline-panel",
+                        h2 { "Outline" }
+                        Outline { entries: outline() }
+                    }
                 }
 
                 div {
@@ -97,6 +217,7 @@ fn OutlinerNode(node_id: NodeId, tree: Signal<Tree>, depth: usize) -> Element {
     let node = tree.read().get_node(node_id).cloned();
     let mut is_editing = use_signal(|| false);
     let mut edit_value = use_signal(|| String::new());
+    let mut table_edit = use_signal(|| None::<Table>);
 
     let Some(node) = node else {
         return rsx! { div { "Node not found" } };
@@ -163,13 +284,19 @@ fn OutlinerNode(node_id: NodeId, tree: Signal<Tree>, depth: usize) -> Element {
                     tabindex: "0",
                     onkeydown: handle_keydown,
                     onclick: move |_| {
-                        if !is_editing() {
-                            edit_value.set(node.content.clone());
-                            is_editing.set(true);
+                        if !is_editing() && table_edit().is_none() {
+                            if let Some(table) = parse_table(&node.content) {
+                                table_edit.set(Some(table));
+                            } else {
+                                edit_value.set(node.content.clone());
+                                is_editing.set(true);
+                            }
                         }
                     },
 
-                    if is_editing() {
+                    if table_edit().is_some() {
+                        TableEditor { node_id, tree, table_edit }
+                    } else if is_editing() {
                         textarea {
                             class: "node-input",
                             value: "{edit_value}",
@@ -210,3 +337,142 @@ fn OutlinerNode(node_id: NodeId, tree: Signal<Tree>, depth: usize) -> Element {
     }
 }
 
+/// Jump-to-section table of contents, nested to match the document's
+/// heading structure. Each entry links to the anchor `render_markdown`
+/// generates for the matching heading.
+#[component]
+fn Outline(entries: Vec<OutlineEntry>) -> Element {
+    rsx! {
+        ul {
+            class: "outline-list",
+            for entry in entries {
+                li {
+                    class: "outline-entry",
+                    a { href: "#{entry.id}", "{entry.title}" }
+                    if !entry.children.is_empty() {
+                        Outline { entries: entry.children.clone() }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Grid editor for a node whose content is a single GFM table: per-cell
+/// inputs plus add/remove row/column buttons, serializing back to the
+/// node's content as canonical pipe syntax whenever a cell loses focus.
+#[component]
+fn TableEditor(node_id: NodeId, tree: Signal<Tree>, mut table_edit: Signal<Option<Table>>) -> Element {
+    let Some(table) = table_edit() else {
+        return rsx! {};
+    };
+
+    let sync_tree = move || {
+        if let Some(current) = table_edit() {
+            tree.write().update_content(node_id, current.to_markdown());
+        }
+    };
+
+    rsx! {
+        div {
+            class: "table-editor",
+            onkeydown: move |evt: Event<KeyboardData>| {
+                let key = evt.data().key();
+
+                if key == Key::Escape {
+                    sync_tree();
+                    table_edit.set(None);
+                }
+
+                // Don't let Tab/Enter bubble up to the node's own keydown
+                // handler — inside the grid they belong to the cell, not
+                // to indenting or creating a sibling block.
+                if matches!(key, Key::Enter | Key::Tab | Key::Escape) {
+                    evt.stop_propagation();
+                }
+            },
+
+            table {
+                thead {
+                    tr {
+                        for (col_idx, cell) in table.header.cells.iter().enumerate() {
+                            th {
+                                input {
+                                    value: "{cell.content}",
+                                    oninput: move |evt| {
+                                        if let Some(current) = table_edit.write().as_mut() {
+                                            current.header.cells[col_idx].content = evt.value();
+                                        }
+                                    },
+                                    onblur: move |_| sync_tree(),
+                                }
+                            }
+                        }
+                        th {
+                            button {
+                                onclick: move |_| {
+                                    if let Some(current) = table_edit.write().as_mut() {
+                                        current.add_column();
+                                    }
+                                    sync_tree();
+                                },
+                                "+ col"
+                            }
+                        }
+                    }
+                }
+                tbody {
+                    for (row_idx, row) in table.rows.iter().enumerate() {
+                        tr {
+                            for (col_idx, cell) in row.cells.iter().enumerate() {
+                                td {
+                                    input {
+                                        value: "{cell.content}",
+                                        oninput: move |evt| {
+                                            if let Some(current) = table_edit.write().as_mut() {
+                                                current.rows[row_idx].cells[col_idx].content = evt.value();
+                                            }
+                                        },
+                                        onblur: move |_| sync_tree(),
+                                    }
+                                }
+                            }
+                            td {
+                                button {
+                                    onclick: move |_| {
+                                        if let Some(current) = table_edit.write().as_mut() {
+                                            current.remove_row(row_idx);
+                                        }
+                                        sync_tree();
+                                    },
+                                    "−"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "table-editor-controls",
+                button {
+                    onclick: move |_| {
+                        if let Some(current) = table_edit.write().as_mut() {
+                            current.add_row();
+                        }
+                        sync_tree();
+                    },
+                    "+ row"
+                }
+                button {
+                    onclick: move |_| {
+                        sync_tree();
+                        table_edit.set(None);
+                    },
+                    "Done"
+                }
+            }
+        }
+    }
+}
+
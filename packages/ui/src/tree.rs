@@ -1,4 +1,7 @@
-use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use comrak::{nodes::AstNode, nodes::NodeValue, parse_document, Arena};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct NodeId(pub usize);
@@ -24,22 +27,75 @@ impl Node {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
 pub struct Tree {
+    nodes: HashMap<NodeId, Node>,
+    root_nodes: Vec<NodeId>,
+    next_id: usize,
+}
+
+/// On-disk/wire shape: nodes as a flat array rather than a map, so the
+/// format doesn't change when the in-memory store does.
+#[derive(Serialize, Deserialize)]
+struct TreeRepr {
     nodes: Vec<Node>,
     root_nodes: Vec<NodeId>,
     next_id: usize,
 }
 
+impl Serialize for Tree {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut nodes: Vec<Node> = self.nodes.values().cloned().collect();
+        nodes.sort_by_key(|n| n.id.0);
+
+        TreeRepr {
+            nodes,
+            root_nodes: self.root_nodes.clone(),
+            next_id: self.next_id,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Tree {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = TreeRepr::deserialize(deserializer)?;
+        let tree = Self {
+            nodes: repr.nodes.into_iter().map(|n| (n.id, n)).collect(),
+            root_nodes: repr.root_nodes,
+            next_id: repr.next_id,
+        };
+
+        tree.assert_invariants();
+        Ok(tree)
+    }
+}
+
 impl Tree {
     pub fn new() -> Self {
         Self {
-            nodes: Vec::new(),
+            nodes: HashMap::new(),
             root_nodes: Vec::new(),
             next_id: 0,
         }
     }
 
+    /// Debug-only sanity check: every id reachable via a `children`,
+    /// `parent`, or `root_nodes` edge must exist in the node map.
+    fn assert_invariants(&self) {
+        debug_assert!(self.root_nodes.iter().all(|id| self.nodes.contains_key(id)));
+
+        for node in self.nodes.values() {
+            debug_assert!(node
+                .parent
+                .map_or(true, |parent_id| self.nodes.contains_key(&parent_id)));
+            debug_assert!(node
+                .children
+                .iter()
+                .all(|child_id| self.nodes.contains_key(child_id)));
+        }
+    }
+
     pub fn add_node(&mut self, content: String, parent: Option<NodeId>) -> NodeId {
         let id = NodeId(self.next_id);
         self.next_id += 1;
@@ -55,24 +111,27 @@ impl Tree {
             self.root_nodes.push(id);
         }
 
-        self.nodes.push(node);
+        self.nodes.insert(id, node);
+        self.assert_invariants();
         id
     }
 
     pub fn get_node(&self, id: NodeId) -> Option<&Node> {
-        self.nodes.iter().find(|n| n.id == id)
+        self.nodes.get(&id)
     }
 
     pub fn get_node_mut(&mut self, id: NodeId) -> Option<&mut Node> {
-        self.nodes.iter_mut().find(|n| n.id == id)
+        self.nodes.get_mut(&id)
     }
 
     pub fn get_root_nodes(&self) -> &[NodeId] {
         &self.root_nodes
     }
 
-    pub fn get_all_nodes(&self) -> &[Node] {
-        &self.nodes
+    pub fn get_all_nodes(&self) -> Vec<&Node> {
+        let mut nodes: Vec<&Node> = self.nodes.values().collect();
+        nodes.sort_by_key(|n| n.id.0);
+        nodes
     }
 
     pub fn toggle_expanded(&mut self, id: NodeId) {
@@ -88,22 +147,24 @@ impl Tree {
     }
 
     pub fn delete_node(&mut self, id: NodeId) {
-        if let Some(node) = self.get_node(id).cloned() {
-            if let Some(parent_id) = node.parent {
-                if let Some(parent) = self.get_node_mut(parent_id) {
-                    parent.children.retain(|&child_id| child_id != id);
-                }
-            } else {
-                self.root_nodes.retain(|&root_id| root_id != id);
-            }
+        let Some(node) = self.get_node(id).cloned() else {
+            return;
+        };
 
-            let children = node.children.clone();
-            for child_id in children {
-                self.delete_node(child_id);
+        if let Some(parent_id) = node.parent {
+            if let Some(parent) = self.get_node_mut(parent_id) {
+                parent.children.retain(|&child_id| child_id != id);
             }
+        } else {
+            self.root_nodes.retain(|&root_id| root_id != id);
+        }
 
-            self.nodes.retain(|n| n.id != id);
+        for child_id in node.children.clone() {
+            self.delete_node(child_id);
         }
+
+        self.nodes.remove(&id);
+        self.assert_invariants();
     }
 
     pub fn add_sibling(&mut self, sibling_id: NodeId, content: String) -> NodeId {
@@ -119,15 +180,15 @@ impl Tree {
                     if let Some(pos) = siblings.iter().position(|&nid| nid == id) {
                         if pos > 0 {
                             let new_parent_id = siblings[pos - 1];
-                            
+
                             if let Some(parent) = self.get_node_mut(parent_id) {
                                 parent.children.retain(|&child_id| child_id != id);
                             }
-                            
+
                             if let Some(new_parent) = self.get_node_mut(new_parent_id) {
                                 new_parent.children.push(id);
                             }
-                            
+
                             if let Some(node) = self.get_node_mut(id) {
                                 node.parent = Some(new_parent_id);
                             }
@@ -136,6 +197,8 @@ impl Tree {
                 }
             }
         }
+
+        self.assert_invariants();
     }
 
     pub fn outdent_node(&mut self, id: NodeId) {
@@ -145,11 +208,11 @@ impl Tree {
                     if let Some(parent) = self.get_node_mut(parent_id) {
                         parent.children.retain(|&child_id| child_id != id);
                     }
-                    
+
                     if let Some(grandparent) = self.get_node_mut(grandparent_id) {
                         grandparent.children.push(id);
                     }
-                    
+
                     if let Some(node) = self.get_node_mut(id) {
                         node.parent = Some(grandparent_id);
                     }
@@ -157,15 +220,17 @@ impl Tree {
                     if let Some(parent) = self.get_node_mut(parent_id) {
                         parent.children.retain(|&child_id| child_id != id);
                     }
-                    
+
                     if let Some(node) = self.get_node_mut(id) {
                         node.parent = None;
                     }
-                    
+
                     self.root_nodes.push(id);
                 }
             }
         }
+
+        self.assert_invariants();
     }
 
     pub fn reorder_children(&mut self, parent_id: Option<NodeId>, new_order: Vec<NodeId>) {
@@ -176,6 +241,29 @@ impl Tree {
         } else {
             self.root_nodes = new_order;
         }
+
+        self.assert_invariants();
+    }
+
+    /// Map each page/block name referenced by a `[[wikilink]]` or
+    /// `((block-ref))` to the nodes that mention it, so a page can show its
+    /// "Linked References". Matching is case-insensitive and trims
+    /// surrounding whitespace; unresolved references (no page/node with
+    /// that name) are included too.
+    pub fn backlinks(&self) -> HashMap<String, Vec<NodeId>> {
+        let mut index: HashMap<String, Vec<NodeId>> = HashMap::new();
+
+        for node in self.nodes.values() {
+            for reference in crate::markdown::extract_references(&node.content) {
+                index.entry(reference).or_default().push(node.id);
+            }
+        }
+
+        for referencing_nodes in index.values_mut() {
+            referencing_nodes.sort_by_key(|id| id.0);
+        }
+
+        index
     }
 
     pub fn move_node(&mut self, node_id: NodeId, new_parent_id: Option<NodeId>, position: usize) {
@@ -188,12 +276,12 @@ impl Tree {
             } else {
                 self.root_nodes.retain(|&id| id != node_id);
             }
-            
+
             // Update node's parent
             if let Some(node) = self.get_node_mut(node_id) {
                 node.parent = new_parent_id;
             }
-            
+
             // Add to new parent
             if let Some(new_parent_id) = new_parent_id {
                 if let Some(new_parent) = self.get_node_mut(new_parent_id) {
@@ -205,6 +293,107 @@ impl Tree {
                 self.root_nodes.insert(pos, node_id);
             }
         }
+
+        self.assert_invariants();
+    }
+}
+
+/// Import a single list item: its own content (if any) becomes the node,
+/// and any nested list becomes its children, recursively.
+fn import_list_item<'a>(tree: &mut Tree, item: &'a AstNode<'a>, parent: Option<NodeId>) {
+    let mut content = String::new();
+    let mut nested_lists = Vec::new();
+
+    for child in item.children() {
+        if matches!(child.data.borrow().value, NodeValue::List(_)) {
+            nested_lists.push(child);
+            continue;
+        }
+
+        let literal = crate::markdown::node_markdown_source(child);
+        if literal.is_empty() {
+            continue;
+        }
+        if !content.is_empty() {
+            content.push(' ');
+        }
+        content.push_str(&literal);
+    }
+
+    let id = tree.add_node(content, parent);
+
+    for list in nested_lists {
+        for child_item in list.children() {
+            import_list_item(tree, child_item, Some(id));
+        }
+    }
+}
+
+impl Tree {
+    /// Parse a markdown document into a `Tree`: each list item becomes a
+    /// node (nested lists become children), and top-level paragraphs or
+    /// headings become root nodes. Each node's content keeps its original
+    /// markdown syntax (headings, emphasis, links, inline math) rather
+    /// than being flattened to plain text, so `render_markdown` on the
+    /// imported node still looks right; collapsed/expanded state isn't
+    /// part of markdown, so every imported node starts expanded.
+    pub fn from_markdown(markdown: &str) -> Tree {
+        let options = crate::markdown::parser_options();
+        let arena = Arena::new();
+        let root = parse_document(&arena, markdown, &options);
+
+        let mut tree = Tree::new();
+
+        for child in root.children() {
+            if matches!(child.data.borrow().value, NodeValue::List(_)) {
+                for item in child.children() {
+                    import_list_item(&mut tree, item, None);
+                }
+            } else {
+                let content = crate::markdown::node_markdown_source(child);
+                if !content.is_empty() {
+                    tree.add_node(content, None);
+                }
+            }
+        }
+
+        tree
+    }
+
+    /// Emit the tree as a nested bullet list, indented two spaces per
+    /// depth level, preserving `children` order. The inverse of
+    /// `from_markdown`.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        for &root_id in &self.root_nodes {
+            self.write_markdown_node(root_id, 0, &mut out);
+        }
+        out
+    }
+
+    fn write_markdown_node(&self, id: NodeId, depth: usize, out: &mut String) {
+        let Some(node) = self.get_node(id) else {
+            return;
+        };
+
+        let indent = "  ".repeat(depth);
+        out.push_str(&indent);
+        out.push_str("- ");
+        // Continuation lines (e.g. a table's rows) are indented to the
+        // item's content column so they stay inside the list item.
+        let mut lines = node.content.lines();
+        out.push_str(lines.next().unwrap_or(""));
+        out.push('\n');
+        for line in lines {
+            out.push_str(&indent);
+            out.push_str("  ");
+            out.push_str(line);
+            out.push('\n');
+        }
+
+        for &child_id in &node.children {
+            self.write_markdown_node(child_id, depth + 1, out);
+        }
     }
 }
 
@@ -213,3 +402,142 @@ impl Default for Tree {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delete_node_removes_whole_subtree() {
+        let mut tree = Tree::new();
+        let root = tree.add_node("root".to_string(), None);
+        let child = tree.add_node("child".to_string(), Some(root));
+        let grandchild = tree.add_node("grandchild".to_string(), Some(child));
+        let sibling = tree.add_node("sibling".to_string(), None);
+
+        tree.delete_node(root);
+
+        assert!(tree.get_node(root).is_none());
+        assert!(tree.get_node(child).is_none());
+        assert!(tree.get_node(grandchild).is_none());
+        assert_eq!(tree.get_root_nodes(), &[sibling]);
+        tree.assert_invariants();
+    }
+
+    #[test]
+    fn test_delete_node_detaches_from_parent() {
+        let mut tree = Tree::new();
+        let root = tree.add_node("root".to_string(), None);
+        let child1 = tree.add_node("child1".to_string(), Some(root));
+        let child2 = tree.add_node("child2".to_string(), Some(root));
+
+        tree.delete_node(child1);
+
+        assert!(tree.get_node(child1).is_none());
+        assert_eq!(tree.get_node(root).unwrap().children, vec![child2]);
+        tree.assert_invariants();
+    }
+
+    #[test]
+    fn test_serde_round_trip_preserves_shape() {
+        let mut tree = Tree::new();
+        let root = tree.add_node("root".to_string(), None);
+        tree.add_node("child".to_string(), Some(root));
+
+        let json = serde_json::to_string(&tree).unwrap();
+        let restored: Tree = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.get_root_nodes(), tree.get_root_nodes());
+        assert_eq!(restored.get_all_nodes().len(), tree.get_all_nodes().len());
+        for node in tree.get_all_nodes() {
+            let restored_node = restored.get_node(node.id).unwrap();
+            assert_eq!(restored_node.content, node.content);
+            assert_eq!(restored_node.parent, node.parent);
+            assert_eq!(restored_node.children, node.children);
+        }
+    }
+
+    #[test]
+    fn test_serde_wire_format_is_flat_node_array() {
+        let mut tree = Tree::new();
+        tree.add_node("root".to_string(), None);
+
+        let value = serde_json::to_value(&tree).unwrap();
+        assert!(value["nodes"].is_array());
+        assert!(value["root_nodes"].is_array());
+        assert!(value["next_id"].is_number());
+    }
+
+    #[test]
+    fn test_invariants_hold_after_mutations() {
+        let mut tree = Tree::new();
+        let root = tree.add_node("root".to_string(), None);
+        let child = tree.add_node("child".to_string(), Some(root));
+        tree.add_node("grandchild".to_string(), Some(child));
+
+        tree.indent_node(child);
+        tree.assert_invariants();
+
+        tree.outdent_node(child);
+        tree.assert_invariants();
+
+        tree.move_node(child, None, 0);
+        tree.assert_invariants();
+
+        tree.toggle_expanded(root);
+        tree.assert_invariants();
+
+        tree.delete_node(root);
+        tree.assert_invariants();
+    }
+
+    #[test]
+    fn test_backlinks_indexes_by_normalized_reference_name() {
+        let mut tree = Tree::new();
+        let referencing = tree.add_node("See [[Other Page]] for details".to_string(), None);
+        tree.add_node("no references here".to_string(), None);
+        let referencing_block_ref = tree.add_node("via ((Other Page)) too".to_string(), None);
+
+        let backlinks = tree.backlinks();
+
+        assert_eq!(backlinks.get("other page"), Some(&vec![referencing, referencing_block_ref]));
+        assert!(!backlinks.contains_key("no references here"));
+    }
+
+    #[test]
+    fn test_from_markdown_preserves_table_as_a_parseable_block() {
+        let markdown = "| A | B |\n| --- | --- |\n| 1 | 2 |\n";
+        let tree = Tree::from_markdown(markdown);
+
+        let nodes = tree.get_all_nodes();
+        assert_eq!(nodes.len(), 1);
+
+        let table = crate::markdown::parse_table(&nodes[0].content)
+            .expect("imported table content should still parse as a table");
+        assert_eq!(table.header.cells[0].content, "A");
+        assert_eq!(table.header.cells[1].content, "B");
+        assert_eq!(table.rows[0].cells[0].content, "1");
+        assert_eq!(table.rows[0].cells[1].content, "2");
+    }
+
+    #[test]
+    fn test_from_markdown_preserves_heading_and_emphasis_syntax() {
+        let tree = Tree::from_markdown("# Title\n\n**bold** and *italic*\n");
+        let contents: Vec<&str> = tree.get_all_nodes().iter().map(|n| n.content.as_str()).collect();
+
+        assert_eq!(contents, vec!["# Title", "**bold** and *italic*"]);
+    }
+
+    #[test]
+    fn test_to_markdown_round_trips_through_from_markdown() {
+        let mut tree = Tree::new();
+        let root = tree.add_node("# Heading".to_string(), None);
+        tree.add_node("**bold** child".to_string(), Some(root));
+
+        let rendered = tree.to_markdown();
+        let reparsed = Tree::from_markdown(&rendered);
+
+        let contents: Vec<&str> = reparsed.get_all_nodes().iter().map(|n| n.content.as_str()).collect();
+        assert_eq!(contents, vec!["# Heading", "**bold** child"]);
+    }
+}